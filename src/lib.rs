@@ -1,8 +1,12 @@
+#![warn(clippy::pedantic)]
 #![crate_name = "safe_ascii"]
 
+use std::io::{self, Write};
+
 /// Type for storing precomputed mapping between u8 to String.
 /// (Subject to change)
 pub struct AsciiMapping {
+    /// Mapping for each of the 256 possible u8 values.
     mapping: [String; 256],
 }
 
@@ -14,9 +18,9 @@ impl AsciiMapping {
     /// let mut exclude: [bool; 256] = [false; 256];
     /// let _ = AsciiMapping::new(&safe_ascii::map_to_mnemonic, exclude);
     /// ```
+    #[must_use]
     pub fn new(map_fn: &dyn Fn(u8) -> String, exclusion_list: [bool; 256]) -> Self {
-        // https://stackoverflow.com/questions/28656387
-        let mut result: [String; 256] = [(); 256].map(|_| String::default());
+        let mut result: [String; 256] = [(); 256].map(|()| String::default());
 
         for i in 0u8..=255 {
             if exclusion_list[i as usize] {
@@ -26,10 +30,40 @@ impl AsciiMapping {
             }
         }
 
-        AsciiMapping { mapping: result }
+        Self { mapping: result }
     }
 
-    /// Convert a u8 according to the mapping.
+    /// Generates a mapping table from u8 to string, as [`AsciiMapping::new`]
+    /// does, then replaces specific byte values with a caller-supplied
+    /// replacement.
+    ///
+    /// ```
+    /// use safe_ascii::AsciiMapping;
+    /// let exclude: [bool; 256] = [false; 256];
+    /// let mapping = AsciiMapping::with_overrides(
+    ///     &safe_ascii::map_to_mnemonic,
+    ///     exclude,
+    ///     &[(0, "<nul>".to_owned())],
+    /// );
+    /// assert_eq!(mapping.convert_u8(0), "<nul>");
+    /// assert_eq!(mapping.convert_u8(1), "(SOH)");
+    /// ```
+    #[must_use]
+    pub fn with_overrides(
+        map_fn: &dyn Fn(u8) -> String,
+        exclusion_list: [bool; 256],
+        overrides: &[(u8, String)],
+    ) -> Self {
+        let mut mapping = Self::new(map_fn, exclusion_list);
+
+        for (byte, replacement) in overrides {
+            mapping.mapping[*byte as usize].clone_from(replacement);
+        }
+
+        mapping
+    }
+
+    /// Convert a `u8` according to the mapping.
     ///
     /// ```
     /// use safe_ascii::AsciiMapping;
@@ -37,42 +71,75 @@ impl AsciiMapping {
     /// let mapping = AsciiMapping::new(&safe_ascii::map_to_mnemonic, exclude);
     /// assert_eq!(mapping.convert_u8(0), "(NUL)");
     /// ```
+    #[must_use]
     pub fn convert_u8(&self, input: u8) -> &str {
         &self.mapping[input as usize]
     }
 
-    /// Convert a u8 according to the mapping.
+    /// Convert up to `size` bytes of a `u8` slice according to the mapping.
+    ///
+    /// Thin wrapper around [`AsciiMapping::write_converted`] that collects
+    /// the result into an owned `String`; prefer `write_converted` on hot
+    /// paths to avoid the extra allocation.
+    ///
+    /// # Panics
+    /// Panics if `size` is greater than `input.len()`. Otherwise it does not
+    /// panic: writing into a `Vec<u8>` cannot fail, and the mapping table
+    /// only ever produces valid UTF-8.
     ///
     /// ```
     /// use safe_ascii::AsciiMapping;
     /// let mut exclude: [bool; 256] = [false; 256];
     /// let mapping = AsciiMapping::new(&safe_ascii::map_to_mnemonic, exclude);
-    /// assert_eq!(mapping.convert_u8_slice(&['h' as u8, ' ' as u8, 'i' as u8]), "h(SP)i");
+    /// assert_eq!(mapping.convert_u8_slice(&['h' as u8, ' ' as u8, 'i' as u8], 3), "h(SP)i");
     /// ```
-    pub fn convert_u8_slice(&self, input: &[u8]) -> String {
-        input
-            .iter()
-            .map(|c| self.mapping[*c as usize].as_ref())
-            .collect::<Vec<&str>>()
-            .join("")
+    #[must_use]
+    pub fn convert_u8_slice(&self, input: &[u8], size: usize) -> String {
+        let mut out = Vec::new();
+        self.write_converted(input, size, &mut out)
+            .expect("writing into a Vec<u8> cannot fail");
+        String::from_utf8(out).expect("mapping table only produces valid UTF-8")
     }
-}
-
-#[test]
-fn test_generate_mapping() {
-    // Exclusion list with all but first excluded
-    let mut exclusion_list: [bool; 256] = [true; 256];
-    exclusion_list[1] = false;
 
-    let mapping = AsciiMapping::new(&map_to_mnemonic, exclusion_list);
-    assert_eq!(mapping.mapping[0], "\0");
-    assert_eq!(mapping.mapping[1], "(SOH)");
-    assert_eq!(mapping.mapping[48], "0");
+    /// Convert up to `size` bytes of a `u8` slice according to the mapping,
+    /// writing each mapped string directly into `out`.
+    ///
+    /// Unlike [`AsciiMapping::convert_u8_slice`], this performs no
+    /// intermediate `Vec` or `String` allocation: each byte's mapping is
+    /// written straight to `out` as it is produced.
+    ///
+    /// # Errors
+    /// Returns an error if writing to `out` fails.
+    ///
+    /// ```
+    /// use safe_ascii::AsciiMapping;
+    /// let mut exclude: [bool; 256] = [false; 256];
+    /// let mapping = AsciiMapping::new(&safe_ascii::map_to_mnemonic, exclude);
+    /// let mut out = Vec::new();
+    /// mapping
+    ///     .write_converted(&['h' as u8, ' ' as u8, 'i' as u8], 3, &mut out)
+    ///     .unwrap();
+    /// assert_eq!(out, b"h(SP)i");
+    /// ```
+    pub fn write_converted<W: Write>(
+        &self,
+        input: &[u8],
+        size: usize,
+        out: &mut W,
+    ) -> io::Result<()> {
+        for c in &input[..size] {
+            out.write_all(self.mapping[*c as usize].as_bytes())?;
+        }
+        Ok(())
+    }
 }
 
 /// Returns a char's mnemonic representation.
 ///
-/// * ASCII characters in range 0x21 to 0x7e are not escaped.
+/// * ASCII characters in range 0x21 to 0x7e are not escaped, except for
+///   `(` and `)` themselves, which are escaped as `(LP)`/`(RP)` so that
+///   `(...)` remains an unambiguous token delimiter for
+///   [`decode_mnemonic_chunk`].
 ///
 /// # Examples
 ///
@@ -84,7 +151,11 @@ fn test_generate_mapping() {
 /// assert_eq!(safe_ascii::map_to_mnemonic('\r' as u8), "(CR)");
 /// assert_eq!(safe_ascii::map_to_mnemonic('a' as u8), "a");
 /// assert_eq!(safe_ascii::map_to_mnemonic('~' as u8), "~");
+/// assert_eq!(safe_ascii::map_to_mnemonic('(' as u8), "(LP)");
+/// assert_eq!(safe_ascii::map_to_mnemonic(')' as u8), "(RP)");
+/// assert_eq!(safe_ascii::map_to_mnemonic(255), "(>7F)");
 /// ```
+#[must_use]
 pub fn map_to_mnemonic(c: u8) -> String {
     match c {
         0 => "(NUL)".to_owned(),
@@ -120,6 +191,10 @@ pub fn map_to_mnemonic(c: u8) -> String {
         30 => "(RS)".to_owned(),
         31 => "(US)".to_owned(),
         32 => "(SP)".to_owned(),
+        // '(' and ')' are escaped even though printable, so that a literal
+        // paren in the input can never be mistaken for a token delimiter.
+        40 => "(LP)".to_owned(),
+        41 => "(RP)".to_owned(),
         33..=126 => (c as char).to_string(), // Printable
         127 => "(DEL)".to_owned(),
         128..=255 => "(>7F)".to_owned(),
@@ -128,32 +203,548 @@ pub fn map_to_mnemonic(c: u8) -> String {
 
 /// Returns a char's escape sequence representation.
 ///
-/// * ASCII characters in range 0x20 to 0x7e are not escaped.
+/// # Examples
+///
+/// ```
+/// use safe_ascii;
+///
+/// assert_eq!(safe_ascii::map_to_escape('\0' as u8), "\\x00");
+/// assert_eq!(safe_ascii::map_to_escape('\t' as u8), "\\x09");
+/// assert_eq!(safe_ascii::map_to_escape('\n' as u8), "\\x0a");
+/// assert_eq!(safe_ascii::map_to_escape('\r' as u8), "\\x0d");
+/// assert_eq!(safe_ascii::map_to_escape('0' as u8), "\\x30");
+/// assert_eq!(safe_ascii::map_to_escape('~' as u8), "\\x7e");
+/// assert_eq!(safe_ascii::map_to_escape(255), "\\xff");
+/// ```
+#[must_use]
+pub fn map_to_escape(c: u8) -> String {
+    format!("\\x{c:02x}")
+}
+
+/// Suppress non-printable ASCII.
 ///
 /// # Examples
 ///
 /// ```
 /// use safe_ascii;
 ///
-/// assert_eq!(safe_ascii::map_to_escape('\0' as u8), "\\0");
-/// assert_eq!(safe_ascii::map_to_escape('\t' as u8), "\\t");
-/// assert_eq!(safe_ascii::map_to_escape('\n' as u8), "\\n");
-/// assert_eq!(safe_ascii::map_to_escape('\r' as u8), "\\r");
-/// assert_eq!(safe_ascii::map_to_escape('0' as u8), "0");
-/// assert_eq!(safe_ascii::map_to_escape('a' as u8), "a");
-/// assert_eq!(safe_ascii::map_to_escape('~' as u8), "~");
+/// assert_eq!(safe_ascii::map_suppress('\0' as u8), "");
+/// assert_eq!(safe_ascii::map_suppress('\t' as u8), "");
+/// assert_eq!(safe_ascii::map_suppress('\n' as u8), "");
+/// assert_eq!(safe_ascii::map_suppress('\r' as u8), "");
+/// assert_eq!(safe_ascii::map_suppress('a' as u8), "a");
+/// assert_eq!(safe_ascii::map_suppress('0' as u8), "0");
+/// assert_eq!(safe_ascii::map_suppress('~' as u8), "~");
 /// ```
 // Map to escape sequence form
-pub fn map_to_escape(c: u8) -> String {
-    // Note: some escape codes are not covered
-    // https://github.com/rust-lang/rfcs/pull/1437
+#[must_use]
+pub fn map_suppress(c: u8) -> String {
+    match c {
+        33..=126 => (c as char).to_string(), // Printable
+        _ => String::new(),
+    }
+}
 
+/// Returns a char's caret (`cat -v`-style) notation.
+///
+/// * C0 control bytes 0x00 to 0x1F are rendered as `^@`..`^_` (the byte plus 0x40).
+/// * DEL (0x7F) is rendered as `^?`.
+/// * High-bit bytes 0x80 to 0xFF are rendered as `M-` followed by the caret
+///   form of the low 7 bits.
+/// * All other (printable) bytes pass through unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use safe_ascii;
+///
+/// assert_eq!(safe_ascii::map_to_caret('\0' as u8), "^@");
+/// assert_eq!(safe_ascii::map_to_caret('\t' as u8), "^I");
+/// assert_eq!(safe_ascii::map_to_caret('\n' as u8), "^J");
+/// assert_eq!(safe_ascii::map_to_caret(0x7f), "^?");
+/// assert_eq!(safe_ascii::map_to_caret('a' as u8), "a");
+/// assert_eq!(safe_ascii::map_to_caret(0x80), "M-^@");
+/// assert_eq!(safe_ascii::map_to_caret(0xe1), "M-a");
+/// ```
+#[must_use]
+pub fn map_to_caret(c: u8) -> String {
     match c {
-        0 => "\\0".to_owned(),
-        9 => "\\t".to_owned(),
-        10 => "\\n".to_owned(),
-        13 => "\\r".to_owned(),
-        32..=126 => (c as char).to_string(), // Printable
-        _ => format!("\\x{:02x}", c),
+        0x00..=0x1F => format!("^{}", (c + 0x40) as char),
+        0x7F => "^?".to_owned(),
+        0x80..=0xFF => format!("M-{}", map_to_caret(c - 0x80)),
+        _ => (c as char).to_string(),
+    }
+}
+
+/// Stateful decoder for `Mode::Unicode`-style output.
+///
+/// Decodes UTF-8 from a byte stream, writing printable scalar values
+/// through verbatim and escaping everything else (non-printable scalar
+/// values, and invalid or incomplete byte sequences) as `\u{HEX}` or
+/// `\xHH` respectively. Unlike [`AsciiMapping`], which maps each byte
+/// independently through a flat 256-entry table, this has to track
+/// partial multi-byte sequences across calls, since a sequence can
+/// straddle a read boundary.
+#[derive(Default)]
+pub struct Utf8Decoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    /// Creates a decoder with no carried-over state.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consumes up to `size` bytes of `input`, decoding UTF-8 and writing
+    /// the result to `out`.
+    ///
+    /// Any trailing bytes that are the start of a multi-byte sequence not
+    /// yet complete are retained internally and combined with the next
+    /// call's input; call [`Utf8Decoder::finish`] once no more input is
+    /// coming to flush a dangling incomplete sequence.
+    ///
+    /// # Errors
+    /// Returns an error if writing to `out` fails.
+    pub fn write_converted<W: Write>(
+        &mut self,
+        input: &[u8],
+        size: usize,
+        out: &mut W,
+    ) -> io::Result<()> {
+        self.pending.extend_from_slice(&input[..size]);
+
+        let mut i = 0;
+        while i < self.pending.len() {
+            let Some(seq_len) = utf8_sequence_len(self.pending[i]) else {
+                write_byte_escape(self.pending[i], out)?;
+                i += 1;
+                continue;
+            };
+
+            if i + seq_len > self.pending.len() {
+                break; // Sequence may still be completed by the next read.
+            }
+
+            let bytes = &self.pending[i..i + seq_len];
+            match decode_scalar(bytes) {
+                Some(c) if c.is_control() => write!(out, "\\u{{{:x}}}", c as u32)?,
+                Some(c) => {
+                    let mut char_buf = [0u8; 4];
+                    out.write_all(c.encode_utf8(&mut char_buf).as_bytes())?;
+                }
+                None => {
+                    // Escape only the lead byte and rescan from the next one,
+                    // so a valid byte after a broken sequence is not swallowed.
+                    write_byte_escape(bytes[0], out)?;
+                    i += 1;
+                    continue;
+                }
+            }
+            i += seq_len;
+        }
+
+        self.pending.drain(..i);
+        Ok(())
+    }
+
+    /// Flushes a dangling incomplete sequence at end-of-input as literal
+    /// `\xHH` escapes.
+    ///
+    /// # Errors
+    /// Returns an error if writing to `out` fails.
+    pub fn finish<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        for &b in &self.pending {
+            write_byte_escape(b, out)?;
+        }
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// Writes a single byte as a `\xHH` escape.
+fn write_byte_escape<W: Write>(b: u8, out: &mut W) -> io::Result<()> {
+    write!(out, "\\x{b:02x}")
+}
+
+/// Returns the length in bytes of the UTF-8 sequence starting with lead
+/// byte `lead`, or `None` if `lead` cannot start a sequence (a stray
+/// continuation byte, or one of the unused `0xF8..=0xFF`).
+fn utf8_sequence_len(lead: u8) -> Option<usize> {
+    match lead {
+        0x00..=0x7F => Some(1),
+        0xC0..=0xDF => Some(2),
+        0xE0..=0xEF => Some(3),
+        0xF0..=0xF7 => Some(4),
+        _ => None,
+    }
+}
+
+/// Decodes a complete UTF-8 sequence into a scalar value, rejecting
+/// overlong encodings, surrogates (U+D800..=U+DFFF), and values beyond
+/// U+10FFFF.
+fn decode_scalar(bytes: &[u8]) -> Option<char> {
+    if bytes[1..].iter().any(|&b| b & 0xC0 != 0x80) {
+        return None; // Not all continuation bytes.
+    }
+
+    let (cp, min): (u32, u32) = match bytes {
+        [b0] => (u32::from(*b0), 0),
+        [b0, b1] => ((u32::from(*b0) & 0x1F) << 6 | (u32::from(*b1) & 0x3F), 0x80),
+        [b0, b1, b2] => (
+            (u32::from(*b0) & 0x0F) << 12 | (u32::from(*b1) & 0x3F) << 6 | (u32::from(*b2) & 0x3F),
+            0x800,
+        ),
+        [b0, b1, b2, b3] => (
+            (u32::from(*b0) & 0x07) << 18
+                | (u32::from(*b1) & 0x3F) << 12
+                | (u32::from(*b2) & 0x3F) << 6
+                | (u32::from(*b3) & 0x3F),
+            0x1_0000,
+        ),
+        _ => return None,
+    };
+
+    if cp < min {
+        return None; // Overlong encoding.
+    }
+
+    char::from_u32(cp) // Also rejects surrogates and values > U+10FFFF.
+}
+
+/// Maps a mnemonic token (e.g. `b"(NUL)"`) back to the byte it encodes.
+///
+/// Returns `None` if `token` is not one of the known mnemonic tokens.
+fn mnemonic_token_to_byte(token: &[u8]) -> Option<u8> {
+    match token {
+        b"(NUL)" => Some(0),
+        b"(SOH)" => Some(1),
+        b"(STX)" => Some(2),
+        b"(ETX)" => Some(3),
+        b"(EOT)" => Some(4),
+        b"(ENQ)" => Some(5),
+        b"(ACK)" => Some(6),
+        b"(BEL)" => Some(7),
+        b"(BS)" => Some(8),
+        b"(HT)" => Some(9),
+        b"(LF)" => Some(10),
+        b"(VT)" => Some(11),
+        b"(FF)" => Some(12),
+        b"(CR)" => Some(13),
+        b"(SO)" => Some(14),
+        b"(SI)" => Some(15),
+        b"(DLE)" => Some(16),
+        b"(DC1)" => Some(17),
+        b"(DC2)" => Some(18),
+        b"(DC3)" => Some(19),
+        b"(DC4)" => Some(20),
+        b"(NAK)" => Some(21),
+        b"(SYN)" => Some(22),
+        b"(ETB)" => Some(23),
+        b"(CAN)" => Some(24),
+        b"(EM)" => Some(25),
+        b"(SUB)" => Some(26),
+        b"(ESC)" => Some(27),
+        b"(FS)" => Some(28),
+        b"(GS)" => Some(29),
+        b"(RS)" => Some(30),
+        b"(US)" => Some(31),
+        b"(SP)" => Some(32),
+        b"(LP)" => Some(40),
+        b"(RP)" => Some(41),
+        b"(DEL)" => Some(127),
+        // Lossy: every byte in 0x80..=0xFF encodes to this same token, so
+        // decoding can only ever recover the first of them.
+        b"(>7F)" => Some(128),
+        _ => None,
     }
 }
+
+/// Longest mnemonic token is `(NUL)`, `(ESC)`, `(DEL)`, `(>7F)`, etc: 5 bytes.
+const MAX_MNEMONIC_TOKEN_LEN: usize = 5;
+
+/// Decodes as many complete mnemonic tokens from `input` as possible
+/// without more data.
+///
+/// Returns the decoded bytes together with the number of leading bytes
+/// of `input` that were consumed. Bytes outside of a `(...)` token are
+/// passed through unchanged. Any unconsumed suffix is the start of a
+/// `(...)` token that may still be completed by more input; append more
+/// bytes and call again, unless `at_eof` is set, in which case a
+/// leftover suffix is treated as a hard error.
+///
+/// # Errors
+/// Returns an error if `input` contains a `(...)` sequence that is not
+/// one of the known mnemonic tokens.
+///
+/// # Examples
+///
+/// ```
+/// use safe_ascii::decode_mnemonic_chunk;
+///
+/// let (decoded, consumed) = decode_mnemonic_chunk(b"h(SP)i", true).unwrap();
+/// assert_eq!(decoded, b"h i");
+/// assert_eq!(consumed, 6);
+/// ```
+pub fn decode_mnemonic_chunk(input: &[u8], at_eof: bool) -> Result<(Vec<u8>, usize), String> {
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    while i < input.len() {
+        if input[i] != b'(' {
+            out.push(input[i]);
+            i += 1;
+            continue;
+        }
+
+        let lookahead = &input[i..];
+        match lookahead
+            .iter()
+            .take(MAX_MNEMONIC_TOKEN_LEN)
+            .position(|&b| b == b')')
+        {
+            Some(rel) => {
+                let token = &lookahead[..=rel];
+                match mnemonic_token_to_byte(token) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += token.len();
+                    }
+                    None => {
+                        return Err(format!(
+                            "Error: Encountered unrecognized mnemonic token \"{}\"",
+                            String::from_utf8_lossy(token)
+                        ));
+                    }
+                }
+            }
+            None if at_eof || lookahead.len() >= MAX_MNEMONIC_TOKEN_LEN => {
+                return Err(format!(
+                    "Error: Encountered truncated or invalid mnemonic token starting at \"{}\"",
+                    String::from_utf8_lossy(lookahead)
+                ));
+            }
+            None => break, // Token may still be completed by the next read.
+        }
+    }
+
+    Ok((out, i))
+}
+
+/// Decodes as many complete `\xHH` escape sequences from `input` as
+/// possible without more data.
+///
+/// Returns the decoded bytes together with the number of leading bytes
+/// of `input` that were consumed. Bytes outside of a `\xHH` sequence are
+/// passed through unchanged. Any unconsumed suffix is the start of a
+/// `\xHH` sequence that may still be completed by more input; append
+/// more bytes and call again, unless `at_eof` is set, in which case a
+/// leftover suffix is treated as a hard error.
+///
+/// # Errors
+/// Returns an error if `input` contains a `\` not followed by a valid
+/// two-digit hex escape.
+///
+/// # Examples
+///
+/// ```
+/// use safe_ascii::decode_escape_chunk;
+///
+/// let (decoded, consumed) = decode_escape_chunk(b"\\x00\\x30", true).unwrap();
+/// assert_eq!(decoded, b"\x00\x30");
+/// assert_eq!(consumed, 8);
+/// ```
+pub fn decode_escape_chunk(input: &[u8], at_eof: bool) -> Result<(Vec<u8>, usize), String> {
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    while i < input.len() {
+        if input[i] != b'\\' {
+            out.push(input[i]);
+            i += 1;
+            continue;
+        }
+
+        if input.len() - i < 4 {
+            if at_eof {
+                return Err(format!(
+                    "Error: Encountered truncated escape sequence \"{}\"",
+                    String::from_utf8_lossy(&input[i..])
+                ));
+            }
+            break; // Sequence may still be completed by the next read.
+        }
+
+        let hex = std::str::from_utf8(&input[i + 2..i + 4])
+            .ok()
+            .filter(|s| s.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|s| u8::from_str_radix(s, 16).ok());
+
+        match hex {
+            Some(byte) if input[i + 1] == b'x' => {
+                out.push(byte);
+                i += 4;
+            }
+            _ => {
+                return Err(format!(
+                    "Error: Encountered invalid escape sequence \"{}\"",
+                    String::from_utf8_lossy(&input[i..i + 4])
+                ));
+            }
+        }
+    }
+
+    Ok((out, i))
+}
+
+#[test]
+fn test_generate_mapping() {
+    // Exclusion list with all but byte 1 (SOH) excluded
+    let mut exclusion_list: [bool; 256] = [true; 256];
+    exclusion_list[1] = false;
+
+    let mapping = AsciiMapping::new(&map_to_mnemonic, exclusion_list);
+    assert_eq!(mapping.mapping[0], "\0");
+    assert_eq!(mapping.mapping[1], "(SOH)");
+    assert_eq!(mapping.mapping[48], "0");
+    assert_eq!(mapping.mapping[255], (255 as u8 as char).to_string());
+}
+
+#[test]
+fn test_map_to_caret() {
+    assert_eq!(map_to_caret(0), "^@");
+    assert_eq!(map_to_caret(31), "^_");
+    assert_eq!(map_to_caret(127), "^?");
+    assert_eq!(map_to_caret(b'~'), "~");
+    assert_eq!(map_to_caret(0x80), "M-^@");
+    assert_eq!(map_to_caret(0xFF), "M-^?");
+}
+
+#[test]
+fn test_with_overrides() {
+    let exclusion_list: [bool; 256] = [false; 256];
+    let overrides = [(0, "<nul>".to_owned()), (27, "<ESC>".to_owned())];
+
+    let mapping = AsciiMapping::with_overrides(&map_to_mnemonic, exclusion_list, &overrides);
+    assert_eq!(mapping.mapping[0], "<nul>");
+    assert_eq!(mapping.mapping[27], "<ESC>");
+    // Bytes with no override keep the mode's own mapping.
+    assert_eq!(mapping.mapping[1], "(SOH)");
+}
+
+#[test]
+fn test_decode_mnemonic_chunk_roundtrip() {
+    let (decoded, consumed) = decode_mnemonic_chunk(b"h(SP)i(LF)", true).unwrap();
+    assert_eq!(decoded, b"h i\n");
+    assert_eq!(consumed, 10);
+}
+
+#[test]
+fn test_decode_mnemonic_chunk_roundtrip_parens() {
+    // Literal parens must round-trip instead of colliding with the
+    // `(...)` token grammar: see `map_to_mnemonic`.
+    assert_eq!(map_to_mnemonic(b'('), "(LP)");
+    assert_eq!(map_to_mnemonic(b')'), "(RP)");
+    let (decoded, consumed) = decode_mnemonic_chunk(b"(LP)hi(RP)", true).unwrap();
+    assert_eq!(decoded, b"(hi)");
+    assert_eq!(consumed, 10);
+}
+
+#[test]
+fn test_decode_mnemonic_chunk_incomplete_token_waits() {
+    // "(N" could still become "(NUL)" with more input, so nothing is consumed yet.
+    let (decoded, consumed) = decode_mnemonic_chunk(b"a(N", false).unwrap();
+    assert_eq!(decoded, b"a");
+    assert_eq!(consumed, 1);
+}
+
+#[test]
+fn test_decode_mnemonic_chunk_unrecognized_token() {
+    assert!(decode_mnemonic_chunk(b"(NOPE)", true).is_err());
+}
+
+#[test]
+fn test_decode_escape_chunk_roundtrip() {
+    let (decoded, consumed) = decode_escape_chunk(b"\\x00\\x30", true).unwrap();
+    assert_eq!(decoded, b"\x00\x30");
+    assert_eq!(consumed, 8);
+}
+
+#[test]
+fn test_decode_escape_chunk_incomplete_sequence_waits() {
+    let (decoded, consumed) = decode_escape_chunk(b"a\\x3", false).unwrap();
+    assert_eq!(decoded, b"a");
+    assert_eq!(consumed, 1);
+}
+
+#[test]
+fn test_decode_escape_chunk_invalid_sequence() {
+    assert!(decode_escape_chunk(b"\\xzz", true).is_err());
+}
+
+#[test]
+fn test_utf8_decoder_passes_through_printable() {
+    let mut decoder = Utf8Decoder::new();
+    let mut out = Vec::new();
+    decoder
+        .write_converted("héllo".as_bytes(), "héllo".len(), &mut out)
+        .unwrap();
+    decoder.finish(&mut out).unwrap();
+    assert_eq!(out, "héllo".as_bytes());
+}
+
+#[test]
+fn test_utf8_decoder_escapes_control_scalar() {
+    let mut decoder = Utf8Decoder::new();
+    let mut out = Vec::new();
+    decoder.write_converted(b"\0", 1, &mut out).unwrap();
+    decoder.finish(&mut out).unwrap();
+    assert_eq!(out, b"\\u{0}");
+}
+
+#[test]
+fn test_utf8_decoder_escapes_invalid_byte() {
+    let mut decoder = Utf8Decoder::new();
+    let mut out = Vec::new();
+    decoder.write_converted(&[0xFF], 1, &mut out).unwrap();
+    decoder.finish(&mut out).unwrap();
+    assert_eq!(out, b"\\xff");
+}
+
+#[test]
+fn test_utf8_decoder_sequence_split_across_calls() {
+    let bytes = "é".as_bytes(); // 2-byte sequence: 0xC3 0xA9
+    let mut decoder = Utf8Decoder::new();
+    let mut out = Vec::new();
+    decoder.write_converted(&bytes[..1], 1, &mut out).unwrap();
+    assert!(out.is_empty()); // Waiting for the rest of the sequence.
+    decoder.write_converted(&bytes[1..], 1, &mut out).unwrap();
+    assert_eq!(out, bytes);
+}
+
+#[test]
+fn test_utf8_decoder_flushes_incomplete_sequence_at_eof() {
+    let mut decoder = Utf8Decoder::new();
+    let mut out = Vec::new();
+    decoder.write_converted(&[0xC3], 1, &mut out).unwrap();
+    decoder.finish(&mut out).unwrap();
+    assert_eq!(out, b"\\xc3");
+}
+
+#[test]
+fn test_utf8_decoder_rejects_overlong_and_surrogate() {
+    let mut decoder = Utf8Decoder::new();
+    let mut out = Vec::new();
+    // Overlong encoding of '/' (0x2F) using 2 bytes.
+    decoder.write_converted(&[0xC0, 0xAF], 2, &mut out).unwrap();
+    assert_eq!(out, b"\\xc0\\xaf");
+
+    let mut decoder = Utf8Decoder::new();
+    let mut out = Vec::new();
+    // Encoded surrogate U+D800.
+    decoder
+        .write_converted(&[0xED, 0xA0, 0x80], 3, &mut out)
+        .unwrap();
+    assert_eq!(out, b"\\xed\\xa0\\x80");
+}
@@ -1,14 +1,20 @@
 #![warn(clippy::pedantic)]
 
 use clap::Parser;
-use safe_ascii::{AsciiMapping, map_suppress, map_to_escape, map_to_mnemonic};
+use safe_ascii::{
+    AsciiMapping, Utf8Decoder, decode_escape_chunk, decode_mnemonic_chunk, map_suppress,
+    map_to_caret, map_to_escape, map_to_mnemonic,
+};
 use std::{
     env, error,
     fs::File,
-    io::{self, BufReader, Read, Write},
+    io::{self, BufReader, BufWriter, Read, Write},
     process,
 };
 
+/// Signature shared by the streaming mnemonic/escape decoders.
+type DecodeFn = fn(&[u8], bool) -> Result<(Vec<u8>, usize), String>;
+
 /// Mode of conversion/suppression.
 #[derive(clap::ValueEnum, Clone)]
 enum Mode {
@@ -18,6 +24,12 @@ enum Mode {
     Escape,
     /// Suppress non-printable characters
     Suppress,
+    /// UTF-8 aware: printable Unicode text passes through, everything else
+    /// (non-printable code points, invalid/incomplete byte sequences) is
+    /// escaped as \u{..} or \xNN
+    Unicode,
+    /// `cat -v`-style notation, e.g. ^@, ^I, ^?, M-x
+    Caret,
 }
 
 /// CLI Definition for clap
@@ -29,13 +41,21 @@ struct Args {
         value_enum,
         short = 'm',
         long = "mode",
-        value_name = "mnemonic|escape|suppress",
+        value_name = "mnemonic|escape|suppress|unicode|caret",
         default_value = "mnemonic",
         num_args(1),
         long_help = "Mode of character conversion/suppression."
     )]
     mode: Mode,
 
+    /// Decode
+    #[arg(
+        long = "decode",
+        long_help = "Reverse the conversion: parse mnemonic/escape output (per -m) back into the original bytes.
+Only supported with -m mnemonic and -m escape; not supported with -m suppress, -m unicode or -m caret."
+    )]
+    decode: bool,
+
     /// Truncate
     #[arg(
         short = 't',
@@ -61,6 +81,16 @@ struct Args {
     )]
     exclude: Vec<String>,
 
+    /// Map file
+    #[arg(
+        long = "map-file",
+        value_name = "path",
+        long_help = "Path to a file of DECIMAL=replacement lines (e.g. \"0=<nul>\") overriding
+the chosen mode's mapping for specific bytes. Bytes not listed keep the
+mode's own mapping. Not supported with --decode or -m unicode."
+    )]
+    map_file: Option<String>,
+
     /// Files
     #[arg(
         value_name = "files",
@@ -73,7 +103,120 @@ Use '-' for stdin."
 
 fn main() -> Result<(), io::Error> {
     let args = Args::parse();
-    let exclude = match parse_exclude(args.exclude) {
+
+    if args.decode {
+        if args.map_file.is_some() {
+            eprintln!("Error: --map-file is not supported with --decode");
+            process::exit(1);
+        }
+        return run_decode(&args);
+    }
+
+    if matches!(args.mode, Mode::Unicode) {
+        if args.map_file.is_some() {
+            eprintln!("Error: --map-file is not supported with unicode mode");
+            process::exit(1);
+        }
+        return run_unicode(&args);
+    }
+
+    run_convert(&args)
+}
+
+/// Prints the standard "could not open file" message and exits with
+/// status 1, matching the error style the rest of the CLI uses.
+fn exit_on_open_error(filename: &str, err: &io::Error) -> ! {
+    eprintln!(
+        "{}: {}: {}",
+        env::args().next().expect("Cannot obtain executable name"),
+        filename,
+        err
+    );
+    std::process::exit(1);
+}
+
+/// Reverses the mnemonic/escape encoding (per `-m`) for each input.
+fn run_decode(args: &Args) -> Result<(), io::Error> {
+    let decode_fn = match args.mode {
+        Mode::Mnemonic => decode_mnemonic_chunk,
+        Mode::Escape => decode_escape_chunk,
+        Mode::Suppress => {
+            eprintln!("Error: --decode is not supported with suppress mode");
+            process::exit(1);
+        }
+        Mode::Unicode => {
+            eprintln!("Error: --decode is not supported with unicode mode");
+            process::exit(1);
+        }
+        Mode::Caret => {
+            eprintln!("Error: --decode is not supported with caret mode");
+            process::exit(1);
+        }
+    };
+
+    let mut truncate = args.truncate;
+
+    if args.files.is_empty() {
+        if truncate == 0 {
+            return Ok(());
+        }
+
+        try_decode(&mut io::stdin(), decode_fn, &mut truncate)?;
+    } else {
+        for filename in &args.files {
+            if truncate == 0 {
+                return Ok(());
+            }
+
+            if filename == "-" {
+                try_decode(&mut io::stdin(), decode_fn, &mut truncate)?;
+                continue;
+            }
+
+            match File::open(filename) {
+                Ok(file) => try_decode(&mut BufReader::new(file), decode_fn, &mut truncate)?,
+                Err(err) => exit_on_open_error(filename, &err),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs UTF-8-aware conversion (`Mode::Unicode`) for each input.
+fn run_unicode(args: &Args) -> Result<(), io::Error> {
+    let mut truncate = args.truncate;
+
+    if args.files.is_empty() {
+        if truncate == 0 {
+            return Ok(());
+        }
+
+        try_process_unicode(&mut io::stdin(), &mut truncate)?;
+    } else {
+        for filename in &args.files {
+            if truncate == 0 {
+                return Ok(());
+            }
+
+            if filename == "-" {
+                try_process_unicode(&mut io::stdin(), &mut truncate)?;
+                continue;
+            }
+
+            match File::open(filename) {
+                Ok(file) => try_process_unicode(&mut BufReader::new(file), &mut truncate)?,
+                Err(err) => exit_on_open_error(filename, &err),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the byte-table conversion (mnemonic/escape/suppress/caret) for each input.
+fn run_convert(args: &Args) -> Result<(), io::Error> {
+    let exclude = match parse_exclude(args.exclude.clone()) {
         Ok(exclude) => exclude,
         Err(e) => {
             eprintln!("{e}");
@@ -85,8 +228,13 @@ fn main() -> Result<(), io::Error> {
         Mode::Mnemonic => map_to_mnemonic,
         Mode::Escape => map_to_escape,
         Mode::Suppress => map_suppress,
+        Mode::Caret => map_to_caret,
+        Mode::Unicode => unreachable!("handled by run_unicode"),
+    };
+    let mapping = match &args.map_file {
+        Some(path) => AsciiMapping::with_overrides(&map_fn, exclude, &load_map_file(path)),
+        None => AsciiMapping::new(&map_fn, exclude),
     };
-    let mapping = AsciiMapping::new(&map_fn, exclude);
 
     let mut truncate = args.truncate;
 
@@ -110,18 +258,9 @@ fn main() -> Result<(), io::Error> {
                 continue;
             }
 
-            let file = File::open(filename);
-            match file {
+            match File::open(filename) {
                 Ok(file) => try_process(&mut BufReader::new(file), &mapping, &mut truncate)?,
-                Err(err) => {
-                    eprintln!(
-                        "{}: {}: {}",
-                        env::args().next().expect("Cannot obtain executable name"),
-                        filename,
-                        err
-                    );
-                    std::process::exit(1);
-                }
+                Err(err) => exit_on_open_error(filename, &err),
             }
         }
     }
@@ -151,7 +290,7 @@ fn process<R: Read>(
     truncate: &mut i128,
 ) -> Result<(), io::Error> {
     let stdout = io::stdout();
-    let mut handle = stdout.lock();
+    let mut handle = BufWriter::new(stdout.lock());
 
     let mut buf: [u8; 16 * 1024] = [0; 16 * 1024];
 
@@ -163,19 +302,15 @@ fn process<R: Read>(
 
         if *truncate < 0 {
             // No truncate limit
-            handle.write_all(mapping.convert_u8_slice(&buf, n).as_bytes())?;
+            mapping.write_converted(&buf, n, &mut handle)?;
         } else if *truncate >= n as i128 {
             // Won't reach limit in this block
-            handle.write_all(mapping.convert_u8_slice(&buf, n).as_bytes())?;
+            mapping.write_converted(&buf, n, &mut handle)?;
             *truncate -= n as i128;
         } else {
             // Will reach limit within this block
             #[allow(clippy::cast_sign_loss)]
-            handle.write_all(
-                mapping
-                    .convert_u8_slice(&buf, *truncate as usize)
-                    .as_bytes(),
-            )?;
+            mapping.write_converted(&buf, *truncate as usize, &mut handle)?;
             *truncate = 0;
         }
         handle.flush()?;
@@ -183,6 +318,124 @@ fn process<R: Read>(
     Ok(())
 }
 
+/// Wrapper for `process_unicode` function, to handle SIGPIPE.
+fn try_process_unicode<R: Read>(reader: &mut R, truncate: &mut i128) -> Result<(), io::Error> {
+    if let Err(e) = process_unicode(reader, truncate) {
+        if e.kind() == io::ErrorKind::BrokenPipe {
+            std::process::exit(141);
+        }
+        Err(e)?;
+    }
+    Ok(())
+}
+
+/// Read from input reader, perform UTF-8-aware conversion, and write to
+/// stdout.
+///
+/// Mirrors `process`, but routes bytes through a [`Utf8Decoder`] rather
+/// than a flat [`AsciiMapping`] table, since a multi-byte UTF-8 sequence
+/// can straddle the 16 KiB read boundary.
+fn process_unicode<R: Read>(reader: &mut R, truncate: &mut i128) -> Result<(), io::Error> {
+    let stdout = io::stdout();
+    let mut handle = BufWriter::new(stdout.lock());
+    let mut decoder = Utf8Decoder::new();
+
+    let mut buf: [u8; 16 * 1024] = [0; 16 * 1024];
+
+    loop {
+        let n = reader.read(&mut buf[..])?;
+        if n == 0 {
+            break; // no more input
+        }
+
+        if *truncate < 0 {
+            // No truncate limit
+            decoder.write_converted(&buf, n, &mut handle)?;
+        } else if *truncate >= n as i128 {
+            // Won't reach limit in this block
+            decoder.write_converted(&buf, n, &mut handle)?;
+            *truncate -= n as i128;
+        } else {
+            // Will reach limit within this block
+            #[allow(clippy::cast_sign_loss)]
+            decoder.write_converted(&buf, *truncate as usize, &mut handle)?;
+            *truncate = 0;
+        }
+        handle.flush()?;
+    }
+
+    decoder.finish(&mut handle)?;
+    handle.flush()?;
+    Ok(())
+}
+
+/// Wrapper for decode function, to handle SIGPIPE.
+fn try_decode<R: Read>(
+    reader: &mut R,
+    decode_fn: DecodeFn,
+    truncate: &mut i128,
+) -> Result<(), io::Error> {
+    if let Err(e) = decode(reader, decode_fn, truncate) {
+        if e.kind() == io::ErrorKind::BrokenPipe {
+            std::process::exit(141);
+        }
+        Err(e)?;
+    }
+    Ok(())
+}
+
+/// Read from input reader, reverse the mnemonic/escape encoding, and write
+/// the original bytes to stdout.
+///
+/// Tokens may straddle the 16 KiB read boundary, so any unparsed tail
+/// returned by `decode_fn` is retained and prefixed onto the next read.
+///
+/// `truncate` limits the number of raw (encoded) input bytes fed to
+/// `decode_fn`, mirroring `process`.
+fn decode<R: Read>(
+    reader: &mut R,
+    decode_fn: DecodeFn,
+    truncate: &mut i128,
+) -> Result<(), io::Error> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+
+    let mut buf: [u8; 16 * 1024] = [0; 16 * 1024];
+    let mut pending: Vec<u8> = Vec::new();
+
+    loop {
+        let n = reader.read(&mut buf[..])?;
+
+        if *truncate < 0 {
+            // No truncate limit
+            pending.extend_from_slice(&buf[..n]);
+        } else if *truncate >= n as i128 {
+            // Won't reach limit in this block
+            pending.extend_from_slice(&buf[..n]);
+            *truncate -= n as i128;
+        } else {
+            // Will reach limit within this block
+            #[allow(clippy::cast_sign_loss)]
+            pending.extend_from_slice(&buf[..*truncate as usize]);
+            *truncate = 0;
+        }
+
+        let at_eof = n == 0;
+
+        let (decoded, consumed) = decode_fn(&pending, at_eof)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        handle.write_all(&decoded)?;
+        handle.flush()?;
+        pending.drain(..consumed);
+
+        if at_eof {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
 /// Parses exclude string
 fn parse_exclude(exclusions: Vec<String>) -> Result<[bool; 256], Box<dyn error::Error>> {
     // Initialize to false
@@ -206,6 +459,54 @@ fn parse_exclude(exclusions: Vec<String>) -> Result<[bool; 256], Box<dyn error::
     Ok(exclude)
 }
 
+/// Reads and parses a `--map-file` at `path`, exiting with status 1 on
+/// either an open error or a malformed line.
+fn load_map_file(path: &str) -> Vec<(u8, String)> {
+    let contents = std::fs::read_to_string(path).unwrap_or_else(|e| exit_on_open_error(path, &e));
+
+    match parse_map_file(&contents) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parses a `--map-file`'s contents into per-byte overrides for
+/// [`AsciiMapping::with_overrides`].
+///
+/// Each non-blank line must be of the form `DECIMAL=replacement`, e.g.
+/// `0=<nul>`. Blank lines are ignored.
+///
+/// # Errors
+/// Returns an error if a non-blank line is missing `=` or its decimal
+/// byte value is unparsable.
+fn parse_map_file(contents: &str) -> Result<Vec<(u8, String)>, Box<dyn error::Error>> {
+    let mut overrides = Vec::new();
+
+    for line in contents.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let Some((byte, replacement)) = line.split_once('=') else {
+            Err(format!(
+                "Error: Encountered malformed line \"{line}\" in map file (expected DECIMAL=replacement)"
+            ))?
+        };
+
+        match str::parse::<u8>(byte) {
+            Ok(byte) => overrides.push((byte, replacement.to_owned())),
+            Err(_) => Err(format!(
+                "Error: Encountered unparsable value \"{byte}\" in map file"
+            ))?,
+        }
+    }
+
+    Ok(overrides)
+}
+
 #[test]
 fn verify_cli() {
     use clap::CommandFactory;
@@ -346,6 +647,89 @@ mod cli {
         assert_eq!(expected, String::from_utf8(output.stdout).unwrap());
     }
 
+    #[test]
+    fn mode_unicode_passes_through_printable_utf8() {
+        let program_path = get_program_path();
+
+        let mut process = Command::new(&program_path)
+            .args(["-m", "unicode"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        process
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all("héllo".as_bytes())
+            .unwrap();
+        let output = process.wait_with_output().unwrap();
+
+        assert_eq!("héllo", String::from_utf8(output.stdout).unwrap());
+    }
+
+    #[test]
+    fn mode_unicode_escapes_control_and_invalid_bytes() {
+        let program_path = get_program_path();
+
+        let mut process = Command::new(&program_path)
+            .args(["-m", "unicode"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        process.stdin.as_mut().unwrap().write_all(&[0, 0xFF]).unwrap();
+        let output = process.wait_with_output().unwrap();
+
+        assert_eq!("\\u{0}\\xff", String::from_utf8(output.stdout).unwrap());
+    }
+
+    #[test]
+    fn decode_unicode_unsupported() {
+        let program_path = get_program_path();
+
+        let process = Command::new(&program_path)
+            .args(["-m", "unicode", "--decode"])
+            .output()
+            .unwrap();
+
+        assert_eq!(1, process.status.code().unwrap());
+    }
+
+    #[test]
+    fn mode_caret() {
+        let program_path = get_program_path();
+
+        let mut process = Command::new(&program_path)
+            .args(["-m", "caret", "-x", ""])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        process
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(&[0, b'a', 127, 0x80])
+            .unwrap();
+        let output = process.wait_with_output().unwrap();
+
+        let expected = "^@a^?M-^@";
+        assert_eq!(expected, String::from_utf8(output.stdout).unwrap());
+    }
+
+    #[test]
+    fn decode_caret_unsupported() {
+        let program_path = get_program_path();
+
+        let process = Command::new(&program_path)
+            .args(["-m", "caret", "--decode"])
+            .output()
+            .unwrap();
+
+        assert_eq!(1, process.status.code().unwrap());
+    }
+
     #[test]
     fn bad_suppression_list() {
         let program_path = get_program_path();
@@ -381,6 +765,224 @@ mod cli {
         assert_eq!(1, process.status.code().unwrap());
     }
 
+    #[test]
+    fn decode_mnemonic_roundtrip() {
+        let program_path = get_program_path();
+
+        let mut encode = Command::new(&program_path)
+            .args(["-m", "mnemonic", "-x", ""])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        encode
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(b"hi\0 there\n")
+            .unwrap();
+        let encoded = encode.wait_with_output().unwrap();
+
+        let mut decode = Command::new(&program_path)
+            .args(["-m", "mnemonic", "--decode"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        decode.stdin.as_mut().unwrap().write_all(&encoded.stdout).unwrap();
+        let decoded = decode.wait_with_output().unwrap();
+
+        assert_eq!(b"hi\0 there\n".to_vec(), decoded.stdout);
+    }
+
+    #[test]
+    fn decode_escape_roundtrip() {
+        let program_path = get_program_path();
+
+        let mut encode = Command::new(&program_path)
+            .args(["-m", "escape", "-x", ""])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        encode
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(b"hi\0 there\n")
+            .unwrap();
+        let encoded = encode.wait_with_output().unwrap();
+
+        let mut decode = Command::new(&program_path)
+            .args(["-m", "escape", "--decode"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        decode.stdin.as_mut().unwrap().write_all(&encoded.stdout).unwrap();
+        let decoded = decode.wait_with_output().unwrap();
+
+        assert_eq!(b"hi\0 there\n".to_vec(), decoded.stdout);
+    }
+
+    #[test]
+    fn decode_mnemonic_roundtrip_parens() {
+        let program_path = get_program_path();
+
+        let mut encode = Command::new(&program_path)
+            .args(["-m", "mnemonic", "-x", ""])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        encode
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(b"(hi) there")
+            .unwrap();
+        let encoded = encode.wait_with_output().unwrap();
+
+        let mut decode = Command::new(&program_path)
+            .args(["-m", "mnemonic", "--decode"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        decode
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(&encoded.stdout)
+            .unwrap();
+        let decoded = decode.wait_with_output().unwrap();
+
+        assert_eq!(b"(hi) there".to_vec(), decoded.stdout);
+    }
+
+    #[test]
+    fn decode_truncate() {
+        let program_path = get_program_path();
+
+        let mut process = Command::new(&program_path)
+            .args(["-m", "mnemonic", "--decode", "-t", "5"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        process
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(b"(NUL)0")
+            .unwrap();
+        let output = process.wait_with_output().unwrap();
+
+        assert_eq!(b"\0".to_vec(), output.stdout);
+        assert_eq!(0, output.status.code().unwrap());
+    }
+
+    #[test]
+    fn decode_invalid_token() {
+        let program_path = get_program_path();
+
+        let mut process = Command::new(&program_path)
+            .args(["-m", "mnemonic", "--decode"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        process.stdin.as_mut().unwrap().write_all(b"(NOPE)").unwrap();
+        let output = process.wait_with_output().unwrap();
+
+        assert_ne!(0, output.status.code().unwrap());
+    }
+
+    #[test]
+    fn decode_suppress_unsupported() {
+        let program_path = get_program_path();
+
+        let process = Command::new(&program_path)
+            .args(["-m", "suppress", "--decode"])
+            .output()
+            .unwrap();
+
+        assert_eq!(1, process.status.code().unwrap());
+    }
+
+    fn write_map_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("safe_ascii_test_map_{name}_{}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn map_file_overrides_specific_bytes() {
+        let program_path = get_program_path();
+        let map_path = write_map_file("overrides", "0=<nul>\n27=<ESC>\n");
+
+        let mut process = Command::new(&program_path)
+            .args(["--map-file", map_path.to_str().unwrap(), "-x", ""])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        process
+            .stdin
+            .as_mut()
+            .unwrap()
+            .write_all(&[0, 27, b'a'])
+            .unwrap();
+        let output = process.wait_with_output().unwrap();
+        std::fs::remove_file(&map_path).unwrap();
+
+        let expected = "<nul><ESC>a";
+        assert_eq!(expected, String::from_utf8(output.stdout).unwrap());
+    }
+
+    #[test]
+    fn map_file_bad_line() {
+        let program_path = get_program_path();
+        let map_path = write_map_file("bad_line", "nope\n");
+
+        let process = Command::new(&program_path)
+            .args(["--map-file", map_path.to_str().unwrap()])
+            .output()
+            .unwrap();
+        std::fs::remove_file(&map_path).unwrap();
+
+        assert_eq!(1, process.status.code().unwrap());
+    }
+
+    #[test]
+    fn map_file_with_decode_unsupported() {
+        let program_path = get_program_path();
+        let map_path = write_map_file("decode", "0=<nul>\n");
+
+        let process = Command::new(&program_path)
+            .args(["--map-file", map_path.to_str().unwrap(), "--decode"])
+            .output()
+            .unwrap();
+        std::fs::remove_file(&map_path).unwrap();
+
+        assert_eq!(1, process.status.code().unwrap());
+    }
+
+    #[test]
+    fn map_file_with_unicode_unsupported() {
+        let program_path = get_program_path();
+        let map_path = write_map_file("unicode", "0=<nul>\n");
+
+        let process = Command::new(&program_path)
+            .args(["--map-file", map_path.to_str().unwrap(), "-m", "unicode"])
+            .output()
+            .unwrap();
+        std::fs::remove_file(&map_path).unwrap();
+
+        assert_eq!(1, process.status.code().unwrap());
+    }
+
     #[test]
     fn empty_exclusion() {
         let program_path = get_program_path();